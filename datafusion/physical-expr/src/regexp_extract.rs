@@ -1,168 +1,1095 @@
-use std::sync::Arc;
-
-use datafusion_common::{DataFusionError, Result, ScalarValue};
-use datafusion_expr::{ScalarFunctionImplementation, Signature, Volatility};
-use datafusion_physical_expr::functions::make_scalar_function;
-use regex::Regex;
-
-/// Creates the `regexp_extract` function for DataFusion
-pub fn create_regexp_extract_udf() -> datafusion_expr::ScalarUDF {
-    let regexp_extract = make_scalar_function(regexp_extract_fn);
-
-    datafusion_expr::create_udf(
-        "regexp_extract",
-        Signature::exact(
-            vec![
-                datafusion_common::DataType::Utf8,
-                datafusion_common::DataType::Utf8,
-                datafusion_common::DataType::Int32,
-            ],
-            Volatility::Immutable,
-        ),
-        Arc::new(datafusion_common::DataType::Utf8),
-        regexp_extract,
-    )
-}
-
-/// The core implementation logic of regexp_extract
-fn regexp_extract_fn(args: &[ScalarValue]) -> Result<ScalarValue> {
-    if args.len() != 3 {
-        return Err(DataFusionError::Internal(
-            "regexp_extract expects exactly 3 arguments".to_string(),
-        ));
-    }
-
-    let input = &args[0];
-    let pattern = &args[1];
-    let idx = &args[2];
-
-    // If any are null, return null
-    if input.is_null() || pattern.is_null() || idx.is_null() {
-        return Ok(ScalarValue::Utf8(None));
-    }
-
-    let input_str = input.as_utf8().unwrap();
-    let pattern_str = pattern.as_utf8().unwrap();
-    let idx = idx.as_i32().unwrap();
-
-    let re = Regex::new(pattern_str).map_err(|e| {
-        DataFusionError::Execution(format!("Invalid regex pattern: {e}"))
-    })?;
-
-    match re.captures(input_str) {
-        Some(caps) => match caps.get(idx as usize) {
-            Some(m) => Ok(ScalarValue::Utf8(Some(m.as_str().to_string()))),
-            None => Ok(ScalarValue::Utf8(Some("".to_string()))),
-        },
-        None => Ok(ScalarValue::Utf8(Some("".to_string()))),
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use datafusion_common::ScalarValue;
-
-    #[test]
-    fn test_basic_match() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
-            ScalarValue::Int32(Some(0)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
-    }
-
-    #[test]
-    fn test_named_groups_not_supported() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some(r"(?P<num>\d+)".to_string())),
-            ScalarValue::Int32(Some(0)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
-    }
-
-    #[test]
-    fn test_group_match() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some(r"(a)(b)(c)".to_string())),
-            ScalarValue::Int32(Some(2)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(Some("b".to_string())));
-    }
-
-    #[test]
-    fn test_index_out_of_bounds() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
-            ScalarValue::Int32(Some(5)), // Too high
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(Some("".to_string())));
-    }
-
-    #[test]
-    fn test_no_match_returns_empty_string() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc".to_string())),
-            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
-            ScalarValue::Int32(Some(1)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(Some("".to_string())));
-    }
-
-    #[test]
-    fn test_invalid_regex_returns_error() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some("(".to_string())), // Invalid regex
-            ScalarValue::Int32(Some(0)),
-        ];
-        let result = regexp_extract_fn(&args);
-        assert!(result.is_err());
-        assert!(
-            result.unwrap_err().to_string().contains("Invalid regex pattern"),
-            "Expected regex error"
-        );
-    }
-
-    #[test]
-    fn test_null_input_returns_null() {
-        let args = vec![
-            ScalarValue::Utf8(None),
-            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
-            ScalarValue::Int32(Some(0)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(None));
-    }
-
-    #[test]
-    fn test_null_pattern_returns_null() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(None),
-            ScalarValue::Int32(Some(0)),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(None));
-    }
-
-    #[test]
-    fn test_null_index_returns_null() {
-        let args = vec![
-            ScalarValue::Utf8(Some("abc123".to_string())),
-            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
-            ScalarValue::Int32(None),
-        ];
-        let result = regexp_extract_fn(&args).unwrap();
-        assert_eq!(result, ScalarValue::Utf8(None));
-    }
-}
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, GenericStringArray, GenericStringBuilder, ListArray, ListBuilder, OffsetSizeTrait,
+    StringArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::cast::{as_large_string_array, as_string_array};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, TypeSignature, Volatility};
+use regex::{Regex, RegexBuilder};
+
+/// Maximum number of distinct patterns kept compiled per thread.
+const REGEX_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<RegexCache> = RefCell::new(RegexCache::new(REGEX_CACHE_CAPACITY));
+}
+
+/// Cache key: the flags string (empty when none were given) paired with the
+/// pattern, kept as separate fields rather than a concatenated string so an
+/// unusual pattern or flags value can never collide with a different
+/// (pattern, flags) pair.
+type RegexCacheKey = (String, String);
+
+/// A small bounded LRU cache mapping a `(flags, pattern)` key to its
+/// compiled `Regex`.
+///
+/// `regexp_extract` is typically called with the same literal pattern (and
+/// flags) for every row in a column, so caching the compiled form avoids
+/// recompiling it once per row.
+struct RegexCache {
+    capacity: usize,
+    entries: HashMap<RegexCacheKey, Arc<Regex>>,
+    // Most-recently-used key is at the back.
+    order: VecDeque<RegexCacheKey>,
+}
+
+impl RegexCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get_or_insert_with(
+        &mut self,
+        key: RegexCacheKey,
+        build: impl FnOnce() -> Result<Regex>,
+    ) -> Result<Arc<Regex>> {
+        if let Some(re) = self.entries.get(&key) {
+            let re = Arc::clone(re);
+            self.touch(&key);
+            return Ok(re);
+        }
+
+        let re = Arc::new(build()?);
+
+        if self.entries.len() >= self.capacity {
+            if let Some(lru_key) = self.order.pop_front() {
+                self.entries.remove(&lru_key);
+            }
+        }
+
+        self.entries.insert(key.clone(), Arc::clone(&re));
+        self.order.push_back(key);
+
+        Ok(re)
+    }
+
+    /// Moves `key` to the most-recently-used position.
+    fn touch(&mut self, key: &RegexCacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(pos).unwrap();
+            self.order.push_back(key);
+        }
+    }
+}
+
+/// Returns the compiled `Regex` for `pattern` and `flags`, compiling and
+/// caching it on the current thread if it isn't already cached. `flags` and
+/// `pattern` are both part of the cache key, so the same pattern compiled
+/// with different flags is cached separately.
+fn compiled_regex(pattern: &str, flags: Option<&str>) -> Result<Arc<Regex>> {
+    let key = (flags.unwrap_or("").to_string(), pattern.to_string());
+
+    REGEX_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .get_or_insert_with(key, || build_regex(pattern, flags))
+    })
+}
+
+/// Compiles `pattern`, applying `flags` (if any) via `RegexBuilder`.
+///
+/// Each character of `flags` maps to a `RegexBuilder` setting: `i` for
+/// case-insensitive matching, `m` for multi-line mode, `s` so `.` matches
+/// newlines, and `x` to ignore whitespace/allow comments in the pattern.
+fn build_regex(pattern: &str, flags: Option<&str>) -> Result<Regex> {
+    let invalid_pattern = |e: regex::Error| {
+        DataFusionError::Execution(format!("Invalid regex pattern: {e}"))
+    };
+
+    match flags {
+        Some(flags) if !flags.is_empty() => {
+            let mut builder = RegexBuilder::new(pattern);
+            for flag in flags.chars() {
+                match flag {
+                    'i' => {
+                        builder.case_insensitive(true);
+                    }
+                    'm' => {
+                        builder.multi_line(true);
+                    }
+                    's' => {
+                        builder.dot_matches_new_line(true);
+                    }
+                    'x' => {
+                        builder.ignore_whitespace(true);
+                    }
+                    other => {
+                        return Err(DataFusionError::Execution(format!(
+                            "regexp_extract: unknown regex flag '{other}'"
+                        )))
+                    }
+                }
+            }
+            builder.build().map_err(invalid_pattern)
+        }
+        _ => Regex::new(pattern).map_err(invalid_pattern),
+    }
+}
+
+/// Creates the `regexp_extract` function for DataFusion
+pub fn create_regexp_extract_udf() -> datafusion_expr::ScalarUDF {
+    datafusion_expr::ScalarUDF::new_from_impl(RegexpExtractUdf::new())
+}
+
+/// `ScalarUDFImpl` for `regexp_extract`.
+///
+/// A plain `Signature` paired with a fixed return type isn't expressive
+/// enough here: `LargeUtf8` input must come back out as `LargeUtf8`, so the
+/// return type has to be computed from the argument types rather than fixed
+/// up front.
+#[derive(Debug)]
+struct RegexpExtractUdf {
+    signature: Signature,
+}
+
+/// Accepts any string type for the first two arguments (input column and
+/// pattern), and a third argument that is either an integer group index or
+/// a `Utf8` group name, so the `regexp_extract*` family composes with
+/// operators that produce `LargeUtf8` or `Utf8View` without an explicit
+/// cast on either string argument. Also includes 4-argument variants with a
+/// trailing `Utf8` regex-flags argument. Shared by `regexp_extract` and
+/// `regexp_extract_all`.
+fn regexp_extract_exact_signatures() -> Vec<TypeSignature> {
+    let string_types = [DataType::Utf8, DataType::LargeUtf8, DataType::Utf8View];
+    let group_types = [DataType::Int32, DataType::Int64, DataType::Utf8];
+
+    let mut signatures = Vec::new();
+    for input_type in &string_types {
+        for pattern_type in &string_types {
+            for group_type in &group_types {
+                signatures.push(TypeSignature::Exact(vec![
+                    input_type.clone(),
+                    pattern_type.clone(),
+                    group_type.clone(),
+                ]));
+                signatures.push(TypeSignature::Exact(vec![
+                    input_type.clone(),
+                    pattern_type.clone(),
+                    group_type.clone(),
+                    DataType::Utf8,
+                ]));
+            }
+        }
+    }
+    signatures
+}
+
+impl RegexpExtractUdf {
+    fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                regexp_extract_exact_signatures(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtractUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_extract"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types.first() {
+            Some(DataType::LargeUtf8) => Ok(DataType::LargeUtf8),
+            _ => Ok(DataType::Utf8),
+        }
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        regexp_extract_columnar(args)
+    }
+}
+
+/// Entry point wired up to the `ScalarUDF`. Unwraps the input column (or
+/// single scalar) and the pattern/index/flags, then runs the array kernel
+/// so a whole batch is processed with one compiled `Regex` and one
+/// `GenericStringBuilder` pass instead of per-row `ScalarValue` dispatch.
+fn regexp_extract_columnar(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(DataFusionError::Internal(
+            "regexp_extract expects 3 or 4 arguments".to_string(),
+        ));
+    }
+
+    // Pattern, index and flags are expected to be literals shared by the
+    // whole column, so a single `Regex` is compiled for the entire batch.
+    let pattern = match &args[1] {
+        ColumnarValue::Scalar(s) => s.clone(),
+        ColumnarValue::Array(_) => {
+            return Err(DataFusionError::NotImplemented(
+                "regexp_extract: pattern argument must be a scalar".to_string(),
+            ))
+        }
+    };
+    let idx = match &args[2] {
+        ColumnarValue::Scalar(s) => s.clone(),
+        ColumnarValue::Array(_) => {
+            return Err(DataFusionError::NotImplemented(
+                "regexp_extract: index argument must be a scalar".to_string(),
+            ))
+        }
+    };
+    let flags = scalar_flags_arg(args, "regexp_extract")?;
+
+    let array = match &args[0] {
+        ColumnarValue::Array(array) => Arc::clone(array),
+        ColumnarValue::Scalar(scalar) => scalar.to_array_of_size(1)?,
+    };
+
+    let result: Arc<dyn Array> = if pattern.is_null()
+        || idx.is_null()
+        || flags.as_ref().is_some_and(ScalarValue::is_null)
+    {
+        match array.data_type() {
+            DataType::LargeUtf8 => Arc::new(arrow::array::LargeStringArray::new_null(array.len())),
+            _ => Arc::new(StringArray::new_null(array.len())),
+        }
+    } else {
+        let flags_str = flags.as_ref().map(scalar_as_str).transpose()?;
+        let re = compiled_regex(scalar_as_str(&pattern)?, flags_str)?;
+        let group = scalar_as_group_ref(&idx)?;
+
+        match array.data_type() {
+            DataType::LargeUtf8 => {
+                let array = as_large_string_array(&array)?;
+                Arc::new(regexp_extract_array(array, &re, &group))
+            }
+            DataType::Utf8View => {
+                // `regexp_extract` still returns `Utf8` for a `Utf8View`
+                // input; only `LargeUtf8` round-trips as `LargeUtf8`.
+                let array = cast(&array, &DataType::Utf8)?;
+                let array = as_string_array(&array)?;
+                Arc::new(regexp_extract_array(array, &re, &group))
+            }
+            _ => {
+                let array = as_string_array(&array)?;
+                Arc::new(regexp_extract_array(array, &re, &group))
+            }
+        }
+    };
+
+    match &args[0] {
+        ColumnarValue::Array(_) => Ok(ColumnarValue::Array(result)),
+        ColumnarValue::Scalar(_) => Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+            &result, 0,
+        )?)),
+    }
+}
+
+/// Which capture group to extract: either a numeric, positional index or a
+/// named group (for patterns using `(?P<name>...)`).
+enum GroupRef {
+    Index(i32),
+    Name(String),
+}
+
+/// Reads the third argument as a [`GroupRef`], accepting `Int32`/`Int64`
+/// for a positional index or `Utf8` for a group name.
+fn scalar_as_group_ref(group: &ScalarValue) -> Result<GroupRef> {
+    match group {
+        ScalarValue::Int32(Some(i)) => Ok(GroupRef::Index(*i)),
+        ScalarValue::Int64(Some(i)) => Ok(GroupRef::Index(*i as i32)),
+        ScalarValue::Utf8(Some(name)) => Ok(GroupRef::Name(name.clone())),
+        other => Err(DataFusionError::Internal(format!(
+            "regexp_extract: unsupported group argument {other:?}"
+        ))),
+    }
+}
+
+/// Reads a string-typed scalar (`Utf8`, `LargeUtf8` or `Utf8View`) as a
+/// `&str`. The pattern and flags arguments accept any of the three string
+/// types, so callers can no longer assume a `Utf8` variant the way
+/// `ScalarValue::as_utf8` would.
+fn scalar_as_str(value: &ScalarValue) -> Result<&str> {
+    match value {
+        ScalarValue::Utf8(Some(s))
+        | ScalarValue::LargeUtf8(Some(s))
+        | ScalarValue::Utf8View(Some(s)) => Ok(s.as_str()),
+        other => Err(DataFusionError::Internal(format!(
+            "regexp_extract: expected a non-null string scalar, got {other:?}"
+        ))),
+    }
+}
+
+/// Reads the optional fourth (flags) argument as a scalar, returning `None`
+/// if the caller only passed 3 arguments.
+fn scalar_flags_arg(args: &[ColumnarValue], fn_name: &str) -> Result<Option<ScalarValue>> {
+    match args.get(3) {
+        None => Ok(None),
+        Some(ColumnarValue::Scalar(s)) => Ok(Some(s.clone())),
+        Some(ColumnarValue::Array(_)) => Err(DataFusionError::NotImplemented(format!(
+            "{fn_name}: flags argument must be a scalar"
+        ))),
+    }
+}
+
+/// Array kernel: applies `re`, extracting capture group `group`, to every
+/// element of `array` in a single pass. Null input elements produce a null
+/// output element; a non-match, an out-of-range index, or an unknown/
+/// non-participating named group produces an empty string, mirroring the
+/// scalar implementation.
+fn regexp_extract_array<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    re: &Regex,
+    group: &GroupRef,
+) -> GenericStringArray<O> {
+    let mut builder = GenericStringBuilder::<O>::new();
+
+    for value in array.iter() {
+        match value {
+            None => builder.append_null(),
+            Some(s) => {
+                let matched = re.captures(s).and_then(|caps| match group {
+                    GroupRef::Index(idx) => caps.get(*idx as usize),
+                    GroupRef::Name(name) => caps.name(name),
+                });
+                match matched {
+                    Some(m) => builder.append_value(m.as_str()),
+                    None => builder.append_value(""),
+                }
+            }
+        }
+    }
+
+    builder.finish()
+}
+
+/// Creates the `regexp_extract_all` function for DataFusion: like
+/// `regexp_extract`, but returns every match in the input as a `List` of
+/// strings instead of only the first.
+pub fn create_regexp_extract_all_udf() -> datafusion_expr::ScalarUDF {
+    datafusion_expr::ScalarUDF::new_from_impl(RegexpExtractAllUdf::new())
+}
+
+#[derive(Debug)]
+struct RegexpExtractAllUdf {
+    signature: Signature,
+}
+
+impl RegexpExtractAllUdf {
+    fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                regexp_extract_exact_signatures(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtractAllUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_extract_all"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        let item_type = match arg_types.first() {
+            Some(DataType::LargeUtf8) => DataType::LargeUtf8,
+            _ => DataType::Utf8,
+        };
+        Ok(DataType::List(Arc::new(Field::new(
+            "item", item_type, true,
+        ))))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        regexp_extract_all_columnar(args)
+    }
+}
+
+/// Entry point wired up to the `regexp_extract_all` `ScalarUDF`. Mirrors
+/// [`regexp_extract_columnar`]'s dispatch on the input string type, reusing
+/// the same compiled-regex cache.
+fn regexp_extract_all_columnar(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(DataFusionError::Internal(
+            "regexp_extract_all expects 3 or 4 arguments".to_string(),
+        ));
+    }
+
+    let pattern = match &args[1] {
+        ColumnarValue::Scalar(s) => s.clone(),
+        ColumnarValue::Array(_) => {
+            return Err(DataFusionError::NotImplemented(
+                "regexp_extract_all: pattern argument must be a scalar".to_string(),
+            ))
+        }
+    };
+    let group = match &args[2] {
+        ColumnarValue::Scalar(s) => s.clone(),
+        ColumnarValue::Array(_) => {
+            return Err(DataFusionError::NotImplemented(
+                "regexp_extract_all: group argument must be a scalar".to_string(),
+            ))
+        }
+    };
+    let flags = scalar_flags_arg(args, "regexp_extract_all")?;
+
+    let array = match &args[0] {
+        ColumnarValue::Array(array) => Arc::clone(array),
+        ColumnarValue::Scalar(scalar) => scalar.to_array_of_size(1)?,
+    };
+
+    let result: Arc<dyn Array> = if pattern.is_null()
+        || group.is_null()
+        || flags.as_ref().is_some_and(ScalarValue::is_null)
+    {
+        let item_type = match array.data_type() {
+            DataType::LargeUtf8 => DataType::LargeUtf8,
+            _ => DataType::Utf8,
+        };
+        let field = Arc::new(Field::new("item", item_type, true));
+        Arc::new(ListArray::new_null(field, array.len()))
+    } else {
+        let flags_str = flags.as_ref().map(scalar_as_str).transpose()?;
+        let re = compiled_regex(scalar_as_str(&pattern)?, flags_str)?;
+        let group = scalar_as_group_ref(&group)?;
+
+        match array.data_type() {
+            DataType::LargeUtf8 => {
+                let array = as_large_string_array(&array)?;
+                Arc::new(regexp_extract_all_array(array, &re, &group))
+            }
+            DataType::Utf8View => {
+                let array = cast(&array, &DataType::Utf8)?;
+                let array = as_string_array(&array)?;
+                Arc::new(regexp_extract_all_array(array, &re, &group))
+            }
+            _ => {
+                let array = as_string_array(&array)?;
+                Arc::new(regexp_extract_all_array(array, &re, &group))
+            }
+        }
+    };
+
+    match &args[0] {
+        ColumnarValue::Array(_) => Ok(ColumnarValue::Array(result)),
+        ColumnarValue::Scalar(_) => Ok(ColumnarValue::Scalar(
+            ScalarValue::try_from_array(&result, 0)?,
+        )),
+    }
+}
+
+/// Array kernel for `regexp_extract_all`: for every element of `array`,
+/// collects every match of `group` into a child string array and emits one
+/// list element per row. A non-matching row gets an empty (not null) list;
+/// a null input row gets a null list.
+fn regexp_extract_all_array<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    re: &Regex,
+    group: &GroupRef,
+) -> ListArray {
+    let values_builder = GenericStringBuilder::<O>::new();
+    let mut builder = ListBuilder::new(values_builder);
+
+    for value in array.iter() {
+        match value {
+            None => builder.append(false),
+            Some(s) => {
+                match group {
+                    GroupRef::Index(0) => {
+                        for m in re.find_iter(s) {
+                            builder.values().append_value(m.as_str());
+                        }
+                    }
+                    GroupRef::Index(idx) => {
+                        for caps in re.captures_iter(s) {
+                            match caps.get(*idx as usize) {
+                                Some(m) => builder.values().append_value(m.as_str()),
+                                None => builder.values().append_value(""),
+                            }
+                        }
+                    }
+                    GroupRef::Name(name) => {
+                        for caps in re.captures_iter(s) {
+                            match caps.name(name) {
+                                Some(m) => builder.values().append_value(m.as_str()),
+                                None => builder.values().append_value(""),
+                            }
+                        }
+                    }
+                }
+                builder.append(true);
+            }
+        }
+    }
+
+    builder.finish()
+}
+
+/// The original scalar-only implementation of `regexp_extract`, kept around
+/// as a focused unit-testing surface for the matching/flags/group logic.
+/// The `ScalarUDFImpl::invoke` path no longer calls this directly: it always
+/// converts its input to an array (see [`regexp_extract_columnar`]) so the
+/// output type tracks the input's concrete string type instead of always
+/// coming back as `Utf8`.
+#[cfg(test)]
+fn regexp_extract_fn(args: &[ScalarValue]) -> Result<ScalarValue> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(DataFusionError::Internal(
+            "regexp_extract expects 3 or 4 arguments".to_string(),
+        ));
+    }
+
+    let input = &args[0];
+    let pattern = &args[1];
+    let idx = &args[2];
+    let flags = args.get(3);
+
+    // If any are null, return null
+    if input.is_null() || pattern.is_null() || idx.is_null() || flags.is_some_and(ScalarValue::is_null)
+    {
+        return Ok(ScalarValue::Utf8(None));
+    }
+
+    let input_str = input.as_utf8().unwrap();
+    let pattern_str = pattern.as_utf8().unwrap();
+    let group = scalar_as_group_ref(idx)?;
+    let flags_str = flags.map(|f| f.as_utf8().unwrap());
+
+    let re = compiled_regex(pattern_str, flags_str)?;
+
+    let matched = re.captures(input_str).and_then(|caps| match &group {
+        GroupRef::Index(idx) => caps.get(*idx as usize),
+        GroupRef::Name(name) => caps.name(name),
+    });
+
+    match matched {
+        Some(m) => Ok(ScalarValue::Utf8(Some(m.as_str().to_string()))),
+        None => Ok(ScalarValue::Utf8(Some("".to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_common::ScalarValue;
+
+    #[test]
+    fn test_basic_match() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(0)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
+    }
+
+    #[test]
+    fn test_named_groups_accessible_positionally() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(?P<num>\d+)".to_string())),
+            ScalarValue::Int32(Some(1)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
+    }
+
+    #[test]
+    fn test_named_group_extraction_by_name() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(?P<num>\d+)".to_string())),
+            ScalarValue::Utf8(Some("num".to_string())),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
+    }
+
+    #[test]
+    fn test_unknown_group_name_returns_empty_string() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(?P<num>\d+)".to_string())),
+            ScalarValue::Utf8(Some("missing".to_string())),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("".to_string())));
+    }
+
+    #[test]
+    fn test_group_match() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(a)(b)(c)".to_string())),
+            ScalarValue::Int32(Some(2)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("b".to_string())));
+    }
+
+    #[test]
+    fn test_index_out_of_bounds() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(5)), // Too high
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("".to_string())));
+    }
+
+    #[test]
+    fn test_no_match_returns_empty_string() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(1)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_regex_returns_error() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some("(".to_string())), // Invalid regex
+            ScalarValue::Int32(Some(0)),
+        ];
+        let result = regexp_extract_fn(&args);
+        assert!(result.is_err());
+        assert!(
+            result.unwrap_err().to_string().contains("Invalid regex pattern"),
+            "Expected regex error"
+        );
+    }
+
+    #[test]
+    fn test_null_input_returns_null() {
+        let args = vec![
+            ScalarValue::Utf8(None),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(0)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(None));
+    }
+
+    #[test]
+    fn test_null_pattern_returns_null() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(None),
+            ScalarValue::Int32(Some(0)),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(None));
+    }
+
+    #[test]
+    fn test_array_kernel_matches_scalar_semantics() {
+        let array = StringArray::from(vec![Some("abc123"), None, Some("no_digits")]);
+        let re = compiled_regex(r"(\d+)", None).unwrap();
+
+        let result = regexp_extract_array(&array, &re, &GroupRef::Index(0));
+
+        assert_eq!(result.value(0), "123");
+        assert!(result.is_null(1));
+        assert_eq!(result.value(2), "");
+    }
+
+    #[test]
+    fn test_extract_all_returns_every_match() {
+        let array = StringArray::from(vec![Some("a1 b22 c333"), None, Some("no digits")]);
+        let re = compiled_regex(r"(\d+)", None).unwrap();
+
+        let result = regexp_extract_all_array(&array, &re, &GroupRef::Index(0));
+
+        let row0 = result.value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(row0.iter().map(Option::unwrap).collect::<Vec<_>>(), vec!["1", "22", "333"]);
+
+        assert!(result.is_null(1));
+
+        let row2 = result.value(2);
+        let row2 = row2.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(row2.len(), 0);
+    }
+
+    #[test]
+    fn test_extract_all_by_group_index() {
+        let array = StringArray::from(vec![Some("a=1,b=2")]);
+        let re = compiled_regex(r"(\w)=(\d)", None).unwrap();
+
+        let result = regexp_extract_all_array(&array, &re, &GroupRef::Index(2));
+
+        let row0 = result.value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(row0.iter().map(Option::unwrap).collect::<Vec<_>>(), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_extract_all_named_group_extraction_by_name() {
+        let array = StringArray::from(vec![Some("a=1,b=2")]);
+        let re = compiled_regex(r"(?P<key>\w)=(?P<val>\d)", None).unwrap();
+
+        let result = regexp_extract_all_array(&array, &re, &GroupRef::Name("val".to_string()));
+
+        let row0 = result.value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(row0.iter().map(Option::unwrap).collect::<Vec<_>>(), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_extract_all_unknown_group_name_returns_empty_strings() {
+        let array = StringArray::from(vec![Some("a=1,b=2")]);
+        let re = compiled_regex(r"(?P<key>\w)=(?P<val>\d)", None).unwrap();
+
+        let result = regexp_extract_all_array(&array, &re, &GroupRef::Name("missing".to_string()));
+
+        let row0 = result.value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(row0.iter().map(Option::unwrap).collect::<Vec<_>>(), vec!["", ""]);
+    }
+
+    #[test]
+    fn test_array_kernel_supports_large_utf8() {
+        use arrow::array::LargeStringArray;
+
+        let array = LargeStringArray::from(vec![Some("abc123"), None, Some("no_digits")]);
+        let re = compiled_regex(r"(\d+)", None).unwrap();
+
+        let result = regexp_extract_array(&array, &re, &GroupRef::Index(0));
+
+        assert_eq!(result.value(0), "123");
+        assert!(result.is_null(1));
+        assert_eq!(result.value(2), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_columnar_large_utf8_scalar_round_trips_as_large_utf8() {
+        use arrow::array::LargeStringArray;
+
+        let args = vec![
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(Some("abc123".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&args).unwrap();
+        match result {
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(Some(s))) => assert_eq!(s, "123"),
+            other => panic!("expected a LargeUtf8 scalar, got {other:?}"),
+        }
+
+        // A null `LargeUtf8` input (e.g. from constant-folding
+        // `CAST(NULL AS LargeUtf8)`) must come back as a null `LargeUtf8`
+        // scalar, not a null `Utf8` scalar.
+        let null_args = vec![
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(None)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&null_args).unwrap();
+        match result {
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(None)) => {}
+            other => panic!("expected a null LargeUtf8 scalar, got {other:?}"),
+        }
+
+        // An array input still behaves the same way as before.
+        let array_args = vec![
+            ColumnarValue::Array(Arc::new(LargeStringArray::from(vec![Some("abc123")]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&array_args).unwrap();
+        match result {
+            ColumnarValue::Array(array) => {
+                let array = array.as_any().downcast_ref::<LargeStringArray>().unwrap();
+                assert_eq!(array.value(0), "123");
+            }
+            other => panic!("expected a LargeUtf8 array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_exact_signatures_accept_large_utf8_pattern() {
+        let signatures = regexp_extract_exact_signatures();
+        let has_large_utf8_pattern = signatures.iter().any(|sig| match sig {
+            TypeSignature::Exact(types) => {
+                types.first() == Some(&DataType::Utf8) && types.get(1) == Some(&DataType::LargeUtf8)
+            }
+            _ => false,
+        });
+        assert!(
+            has_large_utf8_pattern,
+            "expected a signature accepting a LargeUtf8 pattern argument"
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_columnar_accepts_large_utf8_pattern() {
+        let args = vec![
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("abc123".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&args).unwrap();
+        match result {
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(s))) => assert_eq!(s, "123"),
+            other => panic!("expected a Utf8 scalar, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_regexp_extract_columnar_supports_utf8_view() {
+        use arrow::array::StringViewArray;
+
+        let array_args = vec![
+            ColumnarValue::Array(Arc::new(StringViewArray::from(vec![
+                Some("abc123"),
+                None,
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&array_args).unwrap();
+        match result {
+            ColumnarValue::Array(array) => {
+                let array = array.as_any().downcast_ref::<StringArray>().unwrap();
+                assert_eq!(array.value(0), "123");
+                assert!(array.is_null(1));
+            }
+            other => panic!("expected a Utf8 array, got {other:?}"),
+        }
+
+        let scalar_args = vec![
+            ColumnarValue::Scalar(ScalarValue::Utf8View(Some("abc123".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_columnar(&scalar_args).unwrap();
+        match result {
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(s))) => assert_eq!(s, "123"),
+            other => panic!("expected a Utf8 scalar, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_regexp_extract_all_columnar_supports_utf8_view() {
+        use arrow::array::StringViewArray;
+
+        let array_args = vec![
+            ColumnarValue::Array(Arc::new(StringViewArray::from(vec![
+                Some("a1 b22"),
+                None,
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_all_columnar(&array_args).unwrap();
+        let array = match result {
+            ColumnarValue::Array(array) => array,
+            other => panic!("expected an array, got {other:?}"),
+        };
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        let row0 = list_array.value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            row0.iter().map(Option::unwrap).collect::<Vec<_>>(),
+            vec!["1", "22"]
+        );
+        assert!(list_array.is_null(1));
+
+        let scalar_args = vec![
+            ColumnarValue::Scalar(ScalarValue::Utf8View(Some("a1 b22".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_all_columnar(&scalar_args).unwrap();
+        let scalar = match result {
+            ColumnarValue::Scalar(scalar) => scalar,
+            other => panic!("expected a scalar, got {other:?}"),
+        };
+        let array = scalar.to_array().unwrap();
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        let values = list_array.value(0);
+        let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            values.iter().map(Option::unwrap).collect::<Vec<_>>(),
+            vec!["1", "22"]
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_all_columnar_large_utf8_array_and_scalar() {
+        use arrow::array::LargeStringArray;
+
+        let array_args = vec![
+            ColumnarValue::Array(Arc::new(LargeStringArray::from(vec![
+                Some("a1 b22"),
+                None,
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_all_columnar(&array_args).unwrap();
+        let array = match result {
+            ColumnarValue::Array(array) => array,
+            other => panic!("expected an array, got {other:?}"),
+        };
+        let row0 = array.as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        let row0 = row0.as_any().downcast_ref::<LargeStringArray>().unwrap();
+        assert_eq!(
+            row0.iter().map(Option::unwrap).collect::<Vec<_>>(),
+            vec!["1", "22"]
+        );
+        assert!(array
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap()
+            .is_null(1));
+
+        let scalar_args = vec![
+            ColumnarValue::Scalar(ScalarValue::LargeUtf8(Some("a1 b22".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+        ];
+        let result = regexp_extract_all_columnar(&scalar_args).unwrap();
+        let scalar = match result {
+            ColumnarValue::Scalar(scalar) => scalar,
+            other => panic!("expected a scalar, got {other:?}"),
+        };
+        let array = scalar.to_array().unwrap();
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        let values = list_array.value(0);
+        let values = values.as_any().downcast_ref::<LargeStringArray>().unwrap();
+        assert_eq!(
+            values.iter().map(Option::unwrap).collect::<Vec<_>>(),
+            vec!["1", "22"]
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_all_columnar_case_insensitive_flag() {
+        let args = vec![
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("ABC abc Abc")]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(abc)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int32(Some(0))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("i".to_string()))),
+        ];
+        let result = regexp_extract_all_columnar(&args).unwrap();
+        let array = match result {
+            ColumnarValue::Array(array) => array,
+            other => panic!("expected an array, got {other:?}"),
+        };
+        let row0 = array.as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        let row0 = row0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            row0.iter().map(Option::unwrap).collect::<Vec<_>>(),
+            vec!["ABC", "abc", "Abc"]
+        );
+    }
+
+    #[test]
+    fn test_return_type_large_utf8_in_large_utf8_out() {
+        let udf = RegexpExtractUdf::new();
+        let arg_types = [DataType::LargeUtf8, DataType::Utf8, DataType::Int32];
+        assert_eq!(udf.return_type(&arg_types).unwrap(), DataType::LargeUtf8);
+
+        let arg_types = [DataType::Utf8View, DataType::Utf8, DataType::Int32];
+        assert_eq!(udf.return_type(&arg_types).unwrap(), DataType::Utf8);
+    }
+
+    #[test]
+    fn test_pattern_cache_reuses_compiled_regex() {
+        let re1 = compiled_regex(r"(\d+)", None).unwrap();
+        let re2 = compiled_regex(r"(\d+)", None).unwrap();
+        assert!(Arc::ptr_eq(&re1, &re2));
+
+        let re3 = compiled_regex(r"(\w+)", None).unwrap();
+        assert!(!Arc::ptr_eq(&re1, &re3));
+    }
+
+    #[test]
+    fn test_case_insensitive_flag() {
+        let args = vec![
+            ScalarValue::Utf8(Some("ABC123".to_string())),
+            ScalarValue::Utf8(Some(r"(abc)".to_string())),
+            ScalarValue::Int32(Some(0)),
+            ScalarValue::Utf8(Some("i".to_string())),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("ABC".to_string())));
+    }
+
+    #[test]
+    fn test_same_pattern_different_flags_cached_separately() {
+        let case_sensitive = compiled_regex(r"(abc)", None).unwrap();
+        let case_insensitive = compiled_regex(r"(abc)", Some("i")).unwrap();
+
+        assert!(!Arc::ptr_eq(&case_sensitive, &case_insensitive));
+        assert!(case_sensitive.is_match("abc"));
+        assert!(!case_sensitive.is_match("ABC"));
+        assert!(case_insensitive.is_match("ABC"));
+    }
+
+    #[test]
+    fn test_unknown_flag_returns_error() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(0)),
+            ScalarValue::Utf8(Some("z".to_string())),
+        ];
+        let result = regexp_extract_fn(&args);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("unknown regex flag"));
+    }
+
+    #[test]
+    fn test_omitted_flags_behaves_like_no_flags() {
+        let without_flags = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(Some(0)),
+        ];
+        let result = regexp_extract_fn(&without_flags).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(Some("123".to_string())));
+    }
+
+    #[test]
+    fn test_null_index_returns_null() {
+        let args = vec![
+            ScalarValue::Utf8(Some("abc123".to_string())),
+            ScalarValue::Utf8(Some(r"(\d+)".to_string())),
+            ScalarValue::Int32(None),
+        ];
+        let result = regexp_extract_fn(&args).unwrap();
+        assert_eq!(result, ScalarValue::Utf8(None));
+    }
+}